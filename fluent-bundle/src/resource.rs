@@ -1,11 +1,22 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
 use fluent_syntax::ast;
-use fluent_syntax::parser::{parse_runtime, ParserError};
+use fluent_syntax::parser::{parse, parse_runtime, ParserError};
 use yoke::Yoke;
 
 type Resource<'s> = ast::Resource<&'s str>;
 
 type InnerFluentResource = Yoke<Resource<'static>, String>;
 
+/// A lazily-built index mapping message and term names to the position of
+/// their [`Entry`](fluent_syntax::ast::Entry) in the resource body.
+#[derive(Debug, Default)]
+struct IdIndex {
+    messages: HashMap<String, usize>,
+    terms: HashMap<String, usize>,
+}
+
 /// A resource containing a list of localization messages.
 ///
 /// [`FluentResource`] wraps an [`Abstract Syntax Tree`](../fluent_syntax/ast/index.html) produced by the
@@ -38,9 +49,21 @@ type InnerFluentResource = Yoke<Resource<'static>, String>;
 /// A resource owns the source string and the AST contains references
 /// to the slices of the source.
 #[derive(Debug)]
-pub struct FluentResource(InnerFluentResource);
+pub struct FluentResource {
+    res: InnerFluentResource,
+    id_index: OnceLock<IdIndex>,
+}
 
 impl FluentResource {
+    /// Wraps an inner resource, deferring construction of the id index until
+    /// the first lookup.
+    fn from_inner(res: InnerFluentResource) -> Self {
+        Self {
+            res,
+            id_index: OnceLock::new(),
+        }
+    }
+
     /// A fallible constructor of a new [`FluentResource`].
     ///
     /// It takes an encoded `Fluent Translation List` string, parses
@@ -82,8 +105,135 @@ impl FluentResource {
             });
 
         match errors {
-            None => Ok(Self(res)),
-            Some(err) => Err((Self(res), err)),
+            None => Ok(Self::from_inner(res)),
+            Some(err) => Err((Self::from_inner(res), err)),
+        }
+    }
+
+    /// A fallible constructor of a new [`FluentResource`] that preserves
+    /// the full parse tree, including comments.
+    ///
+    /// Unlike [`try_new`](Self::try_new), which drives the runtime-optimized
+    /// parser and strips comments, this constructor drives
+    /// [`fluent_syntax::parser::parse`] and keeps the complete AST:
+    /// resource-level comments, message and term comments, and attributes.
+    /// It is meant for tooling — linters, translation dashboards, editor
+    /// plugins — that needs to introspect a resource without reparsing it.
+    ///
+    /// The comment-bearing entries are exposed through the usual
+    /// [`entries`](Self::entries) and [`get_entry`](Self::get_entry)
+    /// accessors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::FluentResource;
+    /// use fluent_syntax::ast;
+    ///
+    /// let source = r#"
+    ///
+    /// ### A resource-level comment.
+    ///
+    /// # A message comment.
+    /// hello-world = Hello, { $user }!
+    ///
+    /// "#;
+    ///
+    /// let resource = FluentResource::try_new_full(source.to_string())
+    ///     .expect("Failed to parse FTL.");
+    ///
+    /// assert!(resource
+    ///     .entries()
+    ///     .any(|entry| matches!(entry, ast::Entry::ResourceComment(_))));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// As with [`try_new`](Self::try_new), the resource is returned
+    /// regardless of parse errors; in case of errors, the `Err` variant
+    /// contains both the structure and a vector of errors.
+    pub fn try_new_full(source: String) -> Result<Self, (Self, Vec<ParserError>)> {
+        let mut errors = None;
+
+        let res = InnerFluentResource::attach_to_cart(source, |source| match parse(source) {
+            Ok(ast) => ast,
+            Err((ast, err)) => {
+                errors = Some(err);
+                ast
+            }
+        });
+
+        match errors {
+            None => Ok(Self::from_inner(res)),
+            Some(err) => Err((Self::from_inner(res), err)),
+        }
+    }
+
+    /// A fallible constructor that builds a single [`FluentResource`] from
+    /// several FTL source fragments.
+    ///
+    /// The fragments are concatenated into one owned source buffer — with a
+    /// newline appended after each fragment, the last one included, so
+    /// entries at a fragment boundary stay separated — and the whole buffer
+    /// is parsed once. This suits build
+    /// pipelines that assemble a locale from many small per-component files
+    /// but want one merged entry list to hand to a bundle, rather than a
+    /// crowd of tiny resources.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::FluentResource;
+    ///
+    /// let resource = FluentResource::try_concat([
+    ///     "hello = Hello!".to_string(),
+    ///     "goodbye = Goodbye!".to_string(),
+    /// ])
+    /// .expect("Failed to parse FTL fragments.");
+    ///
+    /// assert_eq!(resource.entries().count(), 2);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// As with [`try_new`](Self::try_new), the resource is returned regardless
+    /// of parse errors. The `Err` variant pairs each [`ParserError`] with the
+    /// zero-based index of the fragment it originated in.
+    pub fn try_concat(
+        sources: impl IntoIterator<Item = String>,
+    ) -> Result<Self, (Self, Vec<(usize, ParserError)>)> {
+        let mut combined = String::new();
+        let mut starts = Vec::new();
+        for source in sources {
+            starts.push(combined.len());
+            combined.push_str(&source);
+            combined.push('\n');
+        }
+
+        let mut errors = None;
+        let res =
+            InnerFluentResource::attach_to_cart(combined, |source| match parse_runtime(source) {
+                Ok(ast) => ast,
+                Err((ast, err)) => {
+                    errors = Some(err);
+                    ast
+                }
+            });
+
+        match errors {
+            None => Ok(Self::from_inner(res)),
+            Some(errors) => {
+                let mapped = errors
+                    .into_iter()
+                    .map(|error| {
+                        let fragment = starts
+                            .partition_point(|&start| start <= error.pos.start)
+                            .saturating_sub(1);
+                        (fragment, error)
+                    })
+                    .collect();
+                Err((Self::from_inner(res), mapped))
+            }
         }
     }
 
@@ -106,7 +256,7 @@ impl FluentResource {
     /// );
     /// ```
     pub fn source(&self) -> &str {
-        self.0.backing_cart()
+        self.res.backing_cart()
     }
 
     /// Returns an iterator over [`entries`](fluent_syntax::ast::Entry) of the [`FluentResource`].
@@ -133,7 +283,7 @@ impl FluentResource {
     /// assert!(matches!(resource.entries().next(), Some(ast::Entry::Message(_))));
     /// ```
     pub fn entries(&self) -> impl Iterator<Item = &ast::Entry<&str>> {
-        Yoke::get(&self.0).body.iter()
+        Yoke::get(&self.res).body.iter()
     }
 
     /// Returns an [`Entry`](fluent_syntax::ast::Entry) at the
@@ -157,6 +307,624 @@ impl FluentResource {
     /// assert!(matches!(resource.get_entry(0), Some(ast::Entry::Message(_))));
     /// ```
     pub fn get_entry(&self, idx: usize) -> Option<&ast::Entry<&str>> {
-        Yoke::get(&self.0).body.get(idx)
+        Yoke::get(&self.res).body.get(idx)
+    }
+
+    /// Returns the id index, building it on first access and caching it for
+    /// subsequent lookups.
+    fn id_index(&self) -> &IdIndex {
+        self.id_index.get_or_init(|| {
+            let mut index = IdIndex::default();
+            for (idx, entry) in Yoke::get(&self.res).body.iter().enumerate() {
+                match entry {
+                    ast::Entry::Message(msg) => {
+                        index.messages.entry(msg.id.name.to_string()).or_insert(idx);
+                    }
+                    ast::Entry::Term(term) => {
+                        index.terms.entry(term.id.name.to_string()).or_insert(idx);
+                    }
+                    _ => {}
+                }
+            }
+            index
+        })
+    }
+
+    /// Returns the [`Message`](fluent_syntax::ast::Message) with the given id,
+    /// or `None` if the resource contains no such message.
+    ///
+    /// The first call builds and caches an id index, so repeated lookups run
+    /// in constant time instead of scanning [`entries`](Self::entries).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::FluentResource;
+    ///
+    /// let source = r#"
+    ///
+    /// hello-world = Hello, { $user }!
+    ///
+    /// "#;
+    ///
+    /// let resource = FluentResource::try_new(source.to_string())
+    ///     .expect("Failed to parse FTL.");
+    ///
+    /// let message = resource.get_message("hello-world").unwrap();
+    /// assert_eq!(message.id.name, "hello-world");
+    /// assert!(resource.get_message("missing").is_none());
+    /// ```
+    pub fn get_message(&self, id: &str) -> Option<&ast::Message<&str>> {
+        let idx = *self.id_index().messages.get(id)?;
+        match Yoke::get(&self.res).body.get(idx) {
+            Some(ast::Entry::Message(msg)) => Some(msg),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`Term`](fluent_syntax::ast::Term) with the given id,
+    /// or `None` if the resource contains no such term.
+    ///
+    /// The id is given without the leading `-` that introduces a term in FTL
+    /// source. As with [`get_message`](Self::get_message), the first call
+    /// builds a cached id index so later lookups are constant time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::FluentResource;
+    ///
+    /// let source = r#"
+    ///
+    /// -brand-name = Firefox
+    ///
+    /// "#;
+    ///
+    /// let resource = FluentResource::try_new(source.to_string())
+    ///     .expect("Failed to parse FTL.");
+    ///
+    /// let term = resource.get_term("brand-name").unwrap();
+    /// assert_eq!(term.id.name, "brand-name");
+    /// ```
+    pub fn get_term(&self, id: &str) -> Option<&ast::Term<&str>> {
+        let idx = *self.id_index().terms.get(id)?;
+        match Yoke::get(&self.res).body.get(idx) {
+            Some(ast::Entry::Term(term)) => Some(term),
+            _ => None,
+        }
+    }
+
+    /// Serializes the parsed resource into a compact binary blob that can be
+    /// turned back into a [`FluentResource`] with
+    /// [`from_bytes`](Self::from_bytes) without running the Fluent parser.
+    ///
+    /// Apps that ship many FTL files can parse them once at build time, store
+    /// the resulting blobs, and skip the parse cost on every launch.
+    ///
+    /// The encoding stores the owned source buffer alongside the AST, where
+    /// every string slice is recorded as a `(start, len)` offset into that
+    /// buffer rather than as a copied string.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fluent_bundle::FluentResource;
+    ///
+    /// let resource = FluentResource::try_new("hello-world = Hello!".to_string())
+    ///     .expect("Failed to parse FTL.");
+    ///
+    /// let bytes = resource.to_bytes();
+    /// let restored = FluentResource::from_bytes(&bytes)
+    ///     .expect("Failed to decode resource.");
+    ///
+    /// assert_eq!(restored.source(), resource.source());
+    /// assert_eq!(restored.entries().count(), 1);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let source = self.source();
+        let base = source.as_ptr() as usize;
+        let source_end = base + source.len();
+        let body = map_resource(Yoke::get(&self.res), &|slice: &&str| {
+            // Every AST slice is expected to be a subslice of the owned
+            // source buffer. This holds for parser output, but assert it
+            // before the `as u32` cast so a violated assumption fails loudly
+            // instead of silently wrapping into a corrupt offset.
+            let start = slice.as_ptr() as usize;
+            let end = start + slice.len();
+            assert!(
+                start >= base && end <= source_end,
+                "AST slice is not a subslice of the resource source"
+            );
+            Ok::<_, FluentResourceError>(((start - base) as u32, slice.len() as u32))
+        })
+        .expect("offset extraction is infallible");
+        let serialized = SerializedResource {
+            source: self.source().to_string(),
+            body,
+        };
+        bincode::serialize(&serialized).expect("serializing a resource cannot fail")
+    }
+
+    /// Reconstructs a [`FluentResource`] from a blob produced by
+    /// [`to_bytes`](Self::to_bytes), without invoking the Fluent parser.
+    ///
+    /// The decoded source buffer becomes the owned cart, and every stored
+    /// `(start, len)` offset is turned back into a subslice of that cart, so
+    /// the reconstructed AST borrows from the same buffer the resource owns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the blob cannot be decoded, or if any recorded
+    /// offset falls outside the source buffer or does not land on a char
+    /// boundary (for instance, a truncated or tampered blob).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FluentResourceError> {
+        let SerializedResource { source, body } = bincode::deserialize(bytes)?;
+        let res = InnerFluentResource::try_attach_to_cart(source, |source: &str| {
+            map_resource(&body, &|&(start, len): &(u32, u32)| {
+                let start = start as usize;
+                let end = start + len as usize;
+                source
+                    .get(start..end)
+                    .ok_or(FluentResourceError::OffsetOutOfBounds { start, len: len as usize })
+            })
+        })?;
+        Ok(Self::from_inner(res))
+    }
+
+    /// Maps a set of [`ParserError`]s against the owned source into richer,
+    /// positioned [`Diagnostic`]s carrying 1-based line and column numbers and
+    /// the offending source snippet.
+    ///
+    /// The [`try_new`](Self::try_new) family returns parse errors carrying raw
+    /// byte offsets; this turns them into diagnostics an LSP server or CLI
+    /// linter can render directly, without re-implementing the offset-to-line
+    /// arithmetic. It is typically called on the error path:
+    ///
+    /// ```
+    /// use fluent_bundle::FluentResource;
+    ///
+    /// if let Err((resource, errors)) = FluentResource::try_new("= broken".to_string()) {
+    ///     for diagnostic in resource.diagnostics(&errors) {
+    ///         assert_eq!(diagnostic.line, 1);
+    ///     }
+    /// }
+    /// ```
+    pub fn diagnostics(&self, errors: &[ParserError]) -> Vec<Diagnostic> {
+        errors
+            .iter()
+            .map(|error| Diagnostic::from_parser_error(self.source(), error))
+            .collect()
+    }
+}
+
+/// The severity of a [`Diagnostic`].
+///
+/// Fluent parse problems are always reported as [`Severity::Error`], but the
+/// enum leaves room for linters that want to surface softer hints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A parse problem resolved against the source it occurred in, ready to be
+/// surfaced by an editor or linter.
+///
+/// Unlike [`ParserError`], the position is expressed as 1-based line and
+/// column numbers, and the offending source text is carried inline.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    /// The severity of the problem.
+    pub severity: Severity,
+    /// The 1-based line the problem starts on.
+    pub line: usize,
+    /// The 1-based column the problem starts at, counted in characters.
+    pub column: usize,
+    /// The offending slice of the source.
+    pub snippet: String,
+    /// A human-readable description of the problem.
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Builds a [`Diagnostic`] by resolving a [`ParserError`] against the
+    /// `source` it was produced from.
+    pub fn from_parser_error(source: &str, error: &ParserError) -> Self {
+        let (line, column) = line_column(source, error.pos.start);
+        let range = error.slice.clone().unwrap_or_else(|| error.pos.clone());
+        let snippet = source.get(range).unwrap_or_default().to_string();
+        Self {
+            severity: Severity::Error,
+            line,
+            column,
+            snippet,
+            message: error.kind.to_string(),
+        }
+    }
+}
+
+/// Computes the 1-based line and character column of a byte offset within
+/// `source`. An offset past the end of the source resolves to the final
+/// position.
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    // `ParserError` offsets are raw byte positions and need not land on a
+    // char boundary; back up to the nearest one so slicing cannot panic.
+    let mut offset = offset.min(source.len());
+    while offset > 0 && !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let prefix = &source[..offset];
+    let line = prefix.bytes().filter(|&b| b == b'\n').count() + 1;
+    let line_start = prefix.rfind('\n').map_or(0, |idx| idx + 1);
+    let column = source[line_start..offset].chars().count() + 1;
+    (line, column)
+}
+
+/// A `(start, len)` offset of a string slice into the owned source buffer,
+/// used by the serialized resource format.
+type Offset = (u32, u32);
+
+/// The on-disk shape of a resource produced by
+/// [`FluentResource::to_bytes`]: the owned source buffer plus an AST whose
+/// slices are recorded as offsets into it.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedResource {
+    source: String,
+    body: ast::Resource<Offset>,
+}
+
+/// An error encountered while reconstructing a [`FluentResource`] from its
+/// serialized form via [`FluentResource::from_bytes`].
+#[derive(Debug)]
+pub enum FluentResourceError {
+    /// The binary blob could not be decoded.
+    Decode(bincode::Error),
+    /// A recorded slice offset fell outside the decoded source buffer or did
+    /// not land on a char boundary.
+    OffsetOutOfBounds { start: usize, len: usize },
+}
+
+impl std::fmt::Display for FluentResourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Decode(err) => write!(f, "failed to decode resource: {err}"),
+            Self::OffsetOutOfBounds { start, len } => write!(
+                f,
+                "slice offset {start}..{} is out of bounds of the source buffer",
+                start + len
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FluentResourceError {}
+
+impl From<bincode::Error> for FluentResourceError {
+    fn from(err: bincode::Error) -> Self {
+        Self::Decode(err)
+    }
+}
+
+/// Rebuilds an [`ast::Resource`] over one string type into a resource over
+/// another, applying `f` to every string slice.
+///
+/// Used in both directions of the serialized format: to turn slices into
+/// offsets and offsets back into slices. `f` is fallible so that the decode
+/// path can reject out-of-bounds offsets.
+fn map_resource<S, T, E, F>(node: &ast::Resource<S>, f: &F) -> Result<ast::Resource<T>, E>
+where
+    F: Fn(&S) -> Result<T, E>,
+{
+    Ok(ast::Resource {
+        body: node
+            .body
+            .iter()
+            .map(|entry| map_entry(entry, f))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+fn map_entry<S, T, E, F>(node: &ast::Entry<S>, f: &F) -> Result<ast::Entry<T>, E>
+where
+    F: Fn(&S) -> Result<T, E>,
+{
+    Ok(match node {
+        ast::Entry::Message(msg) => ast::Entry::Message(map_message(msg, f)?),
+        ast::Entry::Term(term) => ast::Entry::Term(map_term(term, f)?),
+        ast::Entry::Comment(comment) => ast::Entry::Comment(map_comment(comment, f)?),
+        ast::Entry::GroupComment(comment) => ast::Entry::GroupComment(map_comment(comment, f)?),
+        ast::Entry::ResourceComment(comment) => {
+            ast::Entry::ResourceComment(map_comment(comment, f)?)
+        }
+        ast::Entry::Junk { content } => ast::Entry::Junk {
+            content: f(content)?,
+        },
+    })
+}
+
+fn map_message<S, T, E, F>(node: &ast::Message<S>, f: &F) -> Result<ast::Message<T>, E>
+where
+    F: Fn(&S) -> Result<T, E>,
+{
+    Ok(ast::Message {
+        id: map_identifier(&node.id, f)?,
+        value: node
+            .value
+            .as_ref()
+            .map(|pattern| map_pattern(pattern, f))
+            .transpose()?,
+        attributes: map_attributes(&node.attributes, f)?,
+        comment: node
+            .comment
+            .as_ref()
+            .map(|comment| map_comment(comment, f))
+            .transpose()?,
+    })
+}
+
+fn map_term<S, T, E, F>(node: &ast::Term<S>, f: &F) -> Result<ast::Term<T>, E>
+where
+    F: Fn(&S) -> Result<T, E>,
+{
+    Ok(ast::Term {
+        id: map_identifier(&node.id, f)?,
+        value: map_pattern(&node.value, f)?,
+        attributes: map_attributes(&node.attributes, f)?,
+        comment: node
+            .comment
+            .as_ref()
+            .map(|comment| map_comment(comment, f))
+            .transpose()?,
+    })
+}
+
+fn map_attributes<S, T, E, F>(
+    nodes: &[ast::Attribute<S>],
+    f: &F,
+) -> Result<Vec<ast::Attribute<T>>, E>
+where
+    F: Fn(&S) -> Result<T, E>,
+{
+    nodes
+        .iter()
+        .map(|attr| {
+            Ok(ast::Attribute {
+                id: map_identifier(&attr.id, f)?,
+                value: map_pattern(&attr.value, f)?,
+            })
+        })
+        .collect()
+}
+
+fn map_comment<S, T, E, F>(node: &ast::Comment<S>, f: &F) -> Result<ast::Comment<T>, E>
+where
+    F: Fn(&S) -> Result<T, E>,
+{
+    Ok(ast::Comment {
+        content: node
+            .content
+            .iter()
+            .map(f)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+fn map_identifier<S, T, E, F>(node: &ast::Identifier<S>, f: &F) -> Result<ast::Identifier<T>, E>
+where
+    F: Fn(&S) -> Result<T, E>,
+{
+    Ok(ast::Identifier {
+        name: f(&node.name)?,
+    })
+}
+
+fn map_pattern<S, T, E, F>(node: &ast::Pattern<S>, f: &F) -> Result<ast::Pattern<T>, E>
+where
+    F: Fn(&S) -> Result<T, E>,
+{
+    Ok(ast::Pattern {
+        elements: node
+            .elements
+            .iter()
+            .map(|element| map_pattern_element(element, f))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+fn map_pattern_element<S, T, E, F>(
+    node: &ast::PatternElement<S>,
+    f: &F,
+) -> Result<ast::PatternElement<T>, E>
+where
+    F: Fn(&S) -> Result<T, E>,
+{
+    Ok(match node {
+        ast::PatternElement::TextElement { value } => ast::PatternElement::TextElement {
+            value: f(value)?,
+        },
+        ast::PatternElement::Placeable { expression } => ast::PatternElement::Placeable {
+            expression: map_expression(expression, f)?,
+        },
+    })
+}
+
+fn map_expression<S, T, E, F>(node: &ast::Expression<S>, f: &F) -> Result<ast::Expression<T>, E>
+where
+    F: Fn(&S) -> Result<T, E>,
+{
+    Ok(match node {
+        ast::Expression::Select { selector, variants } => ast::Expression::Select {
+            selector: map_inline_expression(selector, f)?,
+            variants: variants
+                .iter()
+                .map(|variant| map_variant(variant, f))
+                .collect::<Result<_, _>>()?,
+        },
+        ast::Expression::Inline(inline) => {
+            ast::Expression::Inline(map_inline_expression(inline, f)?)
+        }
+    })
+}
+
+fn map_variant<S, T, E, F>(node: &ast::Variant<S>, f: &F) -> Result<ast::Variant<T>, E>
+where
+    F: Fn(&S) -> Result<T, E>,
+{
+    Ok(ast::Variant {
+        key: match &node.key {
+            ast::VariantKey::Identifier { name } => ast::VariantKey::Identifier { name: f(name)? },
+            ast::VariantKey::NumberLiteral { value } => {
+                ast::VariantKey::NumberLiteral { value: f(value)? }
+            }
+        },
+        value: map_pattern(&node.value, f)?,
+        default: node.default,
+    })
+}
+
+fn map_inline_expression<S, T, E, F>(
+    node: &ast::InlineExpression<S>,
+    f: &F,
+) -> Result<ast::InlineExpression<T>, E>
+where
+    F: Fn(&S) -> Result<T, E>,
+{
+    Ok(match node {
+        ast::InlineExpression::StringLiteral { value } => ast::InlineExpression::StringLiteral {
+            value: f(value)?,
+        },
+        ast::InlineExpression::NumberLiteral { value } => ast::InlineExpression::NumberLiteral {
+            value: f(value)?,
+        },
+        ast::InlineExpression::FunctionReference { id, arguments } => {
+            ast::InlineExpression::FunctionReference {
+                id: map_identifier(id, f)?,
+                arguments: map_call_arguments(arguments, f)?,
+            }
+        }
+        ast::InlineExpression::MessageReference { id, attribute } => {
+            ast::InlineExpression::MessageReference {
+                id: map_identifier(id, f)?,
+                attribute: attribute
+                    .as_ref()
+                    .map(|attr| map_identifier(attr, f))
+                    .transpose()?,
+            }
+        }
+        ast::InlineExpression::TermReference {
+            id,
+            attribute,
+            arguments,
+        } => ast::InlineExpression::TermReference {
+            id: map_identifier(id, f)?,
+            attribute: attribute
+                .as_ref()
+                .map(|attr| map_identifier(attr, f))
+                .transpose()?,
+            arguments: arguments
+                .as_ref()
+                .map(|args| map_call_arguments(args, f))
+                .transpose()?,
+        },
+        ast::InlineExpression::VariableReference { id } => {
+            ast::InlineExpression::VariableReference {
+                id: map_identifier(id, f)?,
+            }
+        }
+        ast::InlineExpression::Placeable { expression } => ast::InlineExpression::Placeable {
+            expression: Box::new(map_expression(expression, f)?),
+        },
+    })
+}
+
+fn map_call_arguments<S, T, E, F>(
+    node: &ast::CallArguments<S>,
+    f: &F,
+) -> Result<ast::CallArguments<T>, E>
+where
+    F: Fn(&S) -> Result<T, E>,
+{
+    Ok(ast::CallArguments {
+        positional: node
+            .positional
+            .iter()
+            .map(|arg| map_inline_expression(arg, f))
+            .collect::<Result<_, _>>()?,
+        named: node
+            .named
+            .iter()
+            .map(|arg| {
+                Ok(ast::NamedArgument {
+                    name: map_identifier(&arg.name, f)?,
+                    value: map_inline_expression(&arg.value, f)?,
+                })
+            })
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_bytes_round_trip_preserves_content() {
+        let source = r#"
+-brand = Firefox
+hello = Hello, { $user }!
+    .title = Welcome
+"#;
+        let resource = FluentResource::try_new(source.to_string()).expect("Failed to parse FTL.");
+        let bytes = resource.to_bytes();
+        let restored = FluentResource::from_bytes(&bytes).expect("Failed to decode resource.");
+
+        assert_eq!(restored.source(), resource.source());
+        assert_eq!(restored.entries().count(), resource.entries().count());
+
+        let message = restored.get_message("hello").expect("missing message");
+        assert_eq!(message.id.name, "hello");
+        // The pattern text survives as a subslice of the reconstructed cart.
+        match &message.value.as_ref().expect("missing value").elements[0] {
+            ast::PatternElement::TextElement { value } => assert_eq!(*value, "Hello, "),
+            other => panic!("unexpected element: {other:?}"),
+        }
+        assert_eq!(message.attributes[0].id.name, "title");
+
+        let term = restored.get_term("brand").expect("missing term");
+        assert_eq!(term.id.name, "brand");
+        match &term.value.elements[0] {
+            ast::PatternElement::TextElement { value } => assert_eq!(*value, "Firefox"),
+            other => panic!("unexpected element: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn from_bytes_rejects_truncated_blob() {
+        let resource =
+            FluentResource::try_new("hello = Hello!".to_string()).expect("Failed to parse FTL.");
+        let mut bytes = resource.to_bytes();
+        bytes.truncate(bytes.len() / 2);
+        // A truncated blob must surface an error, never panic.
+        assert!(matches!(
+            FluentResource::from_bytes(&bytes),
+            Err(FluentResourceError::Decode(_) | FluentResourceError::OffsetOutOfBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn from_bytes_rejects_out_of_bounds_offset() {
+        // A blob that decodes but points a slice past the end of the source
+        // must be rejected rather than producing a dangling slice.
+        let tampered = SerializedResource {
+            source: "hi".to_string(),
+            body: ast::Resource {
+                body: vec![ast::Entry::Junk { content: (100, 5) }],
+            },
+        };
+        let bytes = bincode::serialize(&tampered).expect("serialize");
+        assert!(matches!(
+            FluentResource::from_bytes(&bytes),
+            Err(FluentResourceError::OffsetOutOfBounds { .. })
+        ));
     }
 }